@@ -0,0 +1,97 @@
+//! Minimal `ps-launcher.toml` file access shared by the settings, trust and
+//! signature subsystems
+//!
+//! All three subsystems store their overrides in the same config file next to the
+//! executable, so the file-location and line-parsing logic lives here once
+//! rather than being duplicated per subsystem.
+
+use std::env;
+use std::path::PathBuf;
+
+/// Full path to `ps-launcher.toml` next to the running executable, if resolvable
+pub fn config_file_path() -> Option<PathBuf> {
+    let exe_path = env::current_exe().ok()?;
+    let exe_dir = exe_path.parent()?;
+    Some(exe_dir.join("ps-launcher.toml"))
+}
+
+/// Read `ps-launcher.toml` next to the executable, if it exists and is readable
+///
+/// Returns `None` for a missing or unreadable file - the config file is
+/// entirely optional for every subsystem that consults it.
+pub fn read_config_file() -> Option<String> {
+    std::fs::read_to_string(config_file_path()?).ok()
+}
+
+/// Find the raw value for `key = ...` in a config file's contents
+///
+/// Skips blank lines and `#` comments. This is a small, dependency-free
+/// subset of TOML rather than a full implementation, in keeping with the
+/// launcher's small-binary-size goal.
+pub fn find_key<'a>(contents: &'a str, key: &str) -> Option<&'a str> {
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((found_key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if found_key.trim() == key {
+            return Some(value.trim());
+        }
+    }
+    None
+}
+
+/// Parse a quoted TOML string value, e.g. `"C:\\pwsh.exe"` -> `C:\pwsh.exe`
+pub fn parse_toml_string(value: &str) -> Option<String> {
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+    Some(value.replace("\\\\", "\\"))
+}
+
+/// Parse a TOML array of quoted strings, e.g. `["-NoProfile", "-Verbose"]`
+pub fn parse_toml_string_array(value: &str) -> Option<Vec<String>> {
+    let inner = value.strip_prefix('[')?.strip_suffix(']')?;
+    Some(
+        inner
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .filter_map(parse_toml_string)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_key_present() {
+        let contents = "# comment\nwindows-shell = \"C:\\\\pwsh.exe\"\n";
+        assert_eq!(
+            find_key(contents, "windows-shell"),
+            Some("\"C:\\\\pwsh.exe\"")
+        );
+    }
+
+    #[test]
+    fn test_find_key_missing() {
+        let contents = "other-key = \"value\"\n";
+        assert_eq!(find_key(contents, "windows-shell"), None);
+    }
+
+    #[test]
+    fn test_parse_toml_string_array_trims_entries() {
+        let parsed = parse_toml_string_array("[\"a\", \"b\" ]");
+        assert_eq!(parsed, Some(vec!["a".to_string(), "b".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_toml_string_array_drops_trailing_comma_gap() {
+        let parsed = parse_toml_string_array("[\"a\", \"b\", ]");
+        assert_eq!(parsed, Some(vec!["a".to_string(), "b".to_string()]));
+    }
+}
@@ -11,6 +11,16 @@
 //! - **Error Handling**: Robust error handling with user-friendly messages
 //! - **Small Binary Size**: Optimized for minimal executable size
 //! - **Type Safety**: Strong typing prevents common programming errors
+//! - **Configurable Interpreter**: Shell binary and flags can be overridden via
+//!   CLI (`-Shell`, `-ShellArg`) or a `ps-launcher.toml` next to the executable
+//! - **Script Trust Gate**: Scripts outside a configured trusted root prompt
+//!   for confirmation instead of running automatically
+//! - **Signature Enforcement**: `-RequireSignature` rejects scripts without a
+//!   valid Authenticode signature, optionally pinned to specific thumbprints
+//! - **Live Output Streaming**: stdout/stderr are forwarded as the script
+//!   produces them, with a bounded stderr tail kept for error reporting
+//! - **Inline Commands**: `-Command <scriptblock>` runs without a file on
+//!   disk, under a stricter validator unless `-AllowExpressions` is given
 //!
 //! ## Security Features
 //!
@@ -23,7 +33,13 @@
 
 // Console subsystem required for Command::spawn() to work properly
 
+mod config;
+mod settings;
+mod signature;
+mod trust;
+
 use std::env;
+use std::io::BufRead;
 use std::path::PathBuf;
 use std::process::{exit, Command};
 
@@ -36,6 +52,23 @@ use windows::{
 /// Maximum allowed command line length to prevent resource exhaustion
 const MAX_COMMAND_LENGTH: usize = 8192;
 
+/// Which PowerShell interpreter to prefer when launching a script
+///
+/// # Security
+///
+/// Regardless of mode, only fully-qualified, existence-checked paths are
+/// ever accepted - this selects *which* known-good path to use, it never
+/// relaxes the PATH-hijacking protection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PowerShellMode {
+    /// Prefer PowerShell 7+ (pwsh.exe) if present, otherwise fall back
+    Auto,
+    /// Force PowerShell 7+ (pwsh.exe), fail if not found
+    Core,
+    /// Force the built-in Windows PowerShell 5.1
+    WindowsPowerShell,
+}
+
 /// Characters that are potentially dangerous in command line arguments
 const DANGEROUS_CHARS: &[char] = &[
     ';', '&', '|', '<', '>', '`', '$', '(', ')', '{', '}', '[', ']', '\n', '\r',
@@ -49,7 +82,35 @@ const DANGEROUS_CHARS: &[char] = &[
 #[cfg(windows)]
 fn main() {
     // Parse command line arguments
-    let args: Vec<String> = env::args().collect();
+    let raw_args: Vec<String> = env::args().collect();
+
+    // Launcher flags (-Core, -Shell, -RequireSignature, -AllowExpressions,
+    // ...) are only ever recognized up to the -Script/-Command marker and
+    // its value; everything after that is the script's own parameter list
+    // and must never be rescanned for flags - otherwise a script parameter
+    // that happens to collide with a launcher flag (or is influenced by
+    // whoever invokes this launcher) could be reinterpreted as one.
+    let (head, script_params) = split_launcher_args(&raw_args);
+
+    // Pull out the interpreter-selection flag first; it can appear anywhere
+    // in the launcher-flag segment and is removed before the remaining
+    // arguments are validated positionally.
+    let (powershell_mode, args) = extract_powershell_mode(&head);
+
+    // Pull out -Shell/-ShellArg overrides the same way, then layer the CLI
+    // (highest precedence) over any ps-launcher.toml found next to the exe.
+    let (cli_settings, args) = settings::extract_cli_settings(&args);
+    let resolved_settings = cli_settings.merge(settings::load_config_settings());
+
+    // Pull out -RequireSignature/-PinThumbprint the same way
+    let (require_signature, cli_pinned_thumbprints, args) = signature::extract_cli_options(&args);
+    let pinned_thumbprints: Vec<String> = cli_pinned_thumbprints
+        .into_iter()
+        .chain(signature::configured_pinned_thumbprints())
+        .collect();
+
+    // Pull out the -AllowExpressions opt-in for -Command validation
+    let (allow_expressions, args) = extract_allow_expressions(&args);
 
     // Validate command line arguments
     if let Err(e) = validate_arguments(&args) {
@@ -57,14 +118,42 @@ fn main() {
         exit(1);
     }
 
-    // Extract script path and parameters
-    let script_path = &args[2];
-    let script_params: Vec<String> = args.iter().skip(3).cloned().collect();
+    let mut invocation = Invocation::from_args(&args);
 
-    // Validate and sanitize inputs
-    if let Err(e) = validate_script_path(script_path) {
-        show_error("Script Validation Failed", &e);
-        exit(1);
+    // Validate and sanitize inputs, specific to the invocation mode
+    match &invocation {
+        Invocation::Script(script_path) => {
+            // Expand %ProgramFiles%/%LOCALAPPDATA%-style references before
+            // touching the filesystem - Rust's path APIs don't expand these
+            // themselves, only trust::is_trusted's canonicalization does, so
+            // validating the raw string here would reject the exact paths
+            // the trust gate is meant to recognize.
+            let expanded_script_path = trust::expand_env_vars(script_path);
+            if let Err(e) = validate_script_path(&expanded_script_path) {
+                show_error("Script Validation Failed", &e);
+                exit(1);
+            }
+
+            // Trust gate: auto-run only scripts under a trusted root with no
+            // embedded second command; otherwise ask for confirmation
+            if !trust::is_trusted(script_path, &trust::default_trusted_roots())
+                && !trust::confirm_untrusted_script(script_path)
+            {
+                exit(1);
+            }
+        }
+        Invocation::Command(command) => {
+            if let Err(e) = validate_command_block(command, allow_expressions) {
+                show_error("Command Validation Failed", &e);
+                exit(1);
+            }
+        }
+    }
+
+    // Carry the expanded path forward so the signature check and the actual
+    // invocation value see the same real path that was just validated.
+    if let Invocation::Script(script_path) = &mut invocation {
+        *script_path = trust::expand_env_vars(script_path);
     }
 
     if let Err(e) = validate_parameters(&script_params) {
@@ -72,17 +161,102 @@ fn main() {
         exit(1);
     }
 
-    // Get PowerShell path
-    let powershell_path = match get_powershell_path() {
-        Ok(path) => path,
-        Err(e) => {
-            show_error("PowerShell Not Found", &e);
+    let shell_args = resolved_settings
+        .shell_args
+        .clone()
+        .unwrap_or_else(|| default_shell_args(&invocation));
+    if let Err(e) = validate_parameters(&shell_args) {
+        show_error("Shell Argument Validation Failed", &e);
+        exit(1);
+    }
+
+    if matches!(invocation, Invocation::Command(_))
+        && shell_args
+            .iter()
+            .any(|arg| arg.eq_ignore_ascii_case("-file"))
+    {
+        show_error(
+            "Invalid Invocation",
+            "-Command cannot be combined with a -File shell argument",
+        );
+        exit(1);
+    }
+
+    // Mirror the check above: a -ShellArg override can't reinterpret a
+    // validated script path as an inline -Command expression either
+    if matches!(invocation, Invocation::Script(_))
+        && shell_args
+            .iter()
+            .any(|arg| arg.eq_ignore_ascii_case("-command"))
+    {
+        show_error(
+            "Invalid Invocation",
+            "-Script cannot be combined with a -Command shell argument",
+        );
+        exit(1);
+    }
+
+    // Get PowerShell path: an explicit -Shell/config override beats discovery,
+    // but it is held to the same bar as a discovered path before it's trusted
+    let powershell_path = match resolved_settings.shell.clone() {
+        Some(path) => match validate_shell_override(&path) {
+            Ok(path) => path,
+            Err(e) => {
+                show_error("Invalid Shell Override", &e);
+                exit(1);
+            }
+        },
+        None => match get_powershell_path(powershell_mode) {
+            Ok(path) => path,
+            Err(e) => {
+                show_error("PowerShell Not Found", &e);
+                exit(1);
+            }
+        },
+    };
+
+    if require_signature {
+        let Invocation::Script(script_path) = &invocation else {
+            show_error(
+                "Invalid Invocation",
+                "-RequireSignature applies only to -Script, not -Command",
+            );
+            exit(1);
+        };
+
+        let canonical_script_path = match PathBuf::from(script_path).canonicalize() {
+            Ok(path) => path,
+            Err(e) => {
+                show_error(
+                    "Signature Check Failed",
+                    &format!("Failed to resolve script path: {}", e),
+                );
+                exit(1);
+            }
+        };
+
+        if let Err(e) = signature::verify_signature(
+            &powershell_path,
+            &canonical_script_path,
+            &pinned_thumbprints,
+        ) {
+            show_error("Signature Verification Failed", &e);
             exit(1);
         }
+    }
+
+    let invocation_value = match &invocation {
+        Invocation::Script(script_path) => script_path,
+        Invocation::Command(command) => command,
     };
 
     // Build and execute command
-    match execute_powershell(&powershell_path, script_path, &script_params) {
+    match execute_powershell(
+        &powershell_path,
+        &shell_args,
+        invocation_value,
+        &script_params,
+    ) {
         Ok(exit_code) => exit(exit_code),
         Err(e) => {
             show_error("Execution Failed", &e);
@@ -91,6 +265,104 @@ fn main() {
     }
 }
 
+/// The built-in shell argument set used when no `-ShellArg`/config override is present
+///
+/// Ends in `-File` for a `-Script` invocation or `-Command` for a `-Command`
+/// invocation, matching whichever the interpreter needs right before the
+/// invocation value is appended.
+fn default_shell_args(invocation: &Invocation) -> Vec<String> {
+    let mode_flag = match invocation {
+        Invocation::Script(_) => "-File",
+        Invocation::Command(_) => "-Command",
+    };
+
+    vec![
+        "-NonInteractive".to_string(),
+        "-NoProfile".to_string(),
+        "-ExecutionPolicy".to_string(),
+        "Bypass".to_string(),
+        mode_flag.to_string(),
+    ]
+}
+
+/// Split the raw command line into the launcher-flag segment and the
+/// script's own pass-through parameters
+///
+/// # Arguments
+///
+/// * `raw_args` - The full, unmodified command line including program name
+///
+/// # Returns
+///
+/// `(head, tail)` where `head` is the program name plus every argument up
+/// to and including the `-Script`/`-Command` value (or the whole command
+/// line if no marker is present, so `validate_arguments` still produces
+/// the usage error), and `tail` is everything after that - the script's
+/// own parameters, untouched by any launcher flag extraction below.
+///
+/// # Security
+///
+/// Launcher flags like `-Core`, `-Shell`, `-RequireSignature` etc. must
+/// only ever be recognized in `head`. Scanning the full command line would
+/// let a trailing script parameter that happens to collide with a flag
+/// name - plausible when those parameters are influenced by whoever
+/// invokes this launcher - be stripped out and reinterpreted as a
+/// launcher override instead of passed through to the script.
+fn split_launcher_args(raw_args: &[String]) -> (Vec<String>, Vec<String>) {
+    let marker_index = raw_args
+        .iter()
+        .enumerate()
+        .skip(1)
+        .find(|(_, arg)| matches!(arg.to_lowercase().as_str(), "-script" | "-command"))
+        .map(|(i, _)| i);
+
+    let Some(marker_index) = marker_index else {
+        return (raw_args.to_vec(), Vec::new());
+    };
+
+    let value_index = marker_index + 1;
+    if value_index >= raw_args.len() {
+        return (raw_args.to_vec(), Vec::new());
+    }
+
+    (
+        raw_args[..=value_index].to_vec(),
+        raw_args[value_index + 1..].to_vec(),
+    )
+}
+
+/// Extract the `-Core` / `-WindowsPowerShell` interpreter-selection flag
+///
+/// # Arguments
+///
+/// * `args` - Raw command line arguments including program name
+///
+/// # Returns
+///
+/// The requested `PowerShellMode` together with the argument list stripped
+/// of the flag, so the rest of the pipeline can keep validating positionally
+/// as if the flag was never there.
+fn extract_powershell_mode(args: &[String]) -> (PowerShellMode, Vec<String>) {
+    let mut mode = PowerShellMode::Auto;
+    let remaining: Vec<String> = args
+        .iter()
+        .filter(|arg| match arg.to_lowercase().as_str() {
+            "-core" => {
+                mode = PowerShellMode::Core;
+                false
+            }
+            "-windowspowershell" => {
+                mode = PowerShellMode::WindowsPowerShell;
+                false
+            }
+            _ => true,
+        })
+        .cloned()
+        .collect();
+
+    (mode, remaining)
+}
+
 /// Validate command line arguments
 ///
 /// # Arguments
@@ -100,16 +372,41 @@ fn main() {
 /// # Returns
 ///
 /// `Ok(())` if arguments are valid, `Err` with description otherwise
+///
+/// # Security
+///
+/// Accepts either `-Script <path>` or `-Command <scriptblock>` as the
+/// invocation mode - never both, since each is validated and sanitized
+/// differently downstream.
 fn validate_arguments(args: &[String]) -> std::result::Result<(), String> {
     if args.len() < 3 {
         return Err(show_usage());
     }
 
-    if args[1].to_lowercase() != "-script" {
-        return Err(show_usage());
+    match args[1].to_lowercase().as_str() {
+        "-script" | "-command" => Ok(()),
+        _ => Err(show_usage()),
     }
+}
 
-    Ok(())
+/// Which invocation mode was requested on the command line
+#[derive(Debug, Clone)]
+enum Invocation {
+    /// `-Script <path>`: run a `.ps1` file via the interpreter's `-File` mode
+    Script(String),
+    /// `-Command <scriptblock>`: run an inline block via the interpreter's `-Command` mode
+    Command(String),
+}
+
+impl Invocation {
+    /// Parse the already-validated `-Script`/`-Command` flag and its value
+    fn from_args(args: &[String]) -> Invocation {
+        let value = args[2].clone();
+        match args[1].to_lowercase().as_str() {
+            "-command" => Invocation::Command(value),
+            _ => Invocation::Script(value),
+        }
+    }
 }
 
 /// Generate usage message
@@ -120,15 +417,23 @@ fn validate_arguments(args: &[String]) -> std::result::Result<(), String> {
 fn show_usage() -> String {
     String::from(
         "PS-Launcher Usage:\n\n\
-        ps-launcher.exe -Script <script_path> [parameters]\n\n\
+        ps-launcher.exe [-Core | -WindowsPowerShell] -Script <script_path> [parameters]\n\
+        ps-launcher.exe [-AllowExpressions] -Command <scriptblock> [parameters]\n\n\
         Examples:\n\
         \u{00A0}\u{00A0}ps-launcher.exe -Script test.ps1\n\
         \u{00A0}\u{00A0}ps-launcher.exe -Script test.ps1 -FilePath \"C:\\temp\\test.txt\"\n\
-        \u{00A0}\u{00A0}ps-launcher.exe -Script test.ps1 -Name \"John Doe\" -Verbose\n\n\
+        \u{00A0}\u{00A0}ps-launcher.exe -Script test.ps1 -Name \"John Doe\" -Verbose\n\
+        \u{00A0}\u{00A0}ps-launcher.exe -Core -Script test.ps1\n\
+        \u{00A0}\u{00A0}ps-launcher.exe -Command \"Get-Date\"\n\n\
         Notes:\n\
         - Parameters with spaces are automatically quoted\n\
         - Dangerous characters (; & | < > ` $ etc.) are rejected\n\
-        - Returns 0 for success, 1 for errors",
+        - Returns 0 for success, 1 for errors\n\
+        - -Core forces PowerShell 7+ (pwsh.exe); -WindowsPowerShell forces 5.1\n\
+        - Without either flag, pwsh.exe is used when found, else Windows PowerShell\n\
+        - -RequireSignature rejects scripts without a valid Authenticode signature\n\
+        - -Command runs an inline scriptblock instead of a file; it cannot be combined with -Script\n\
+        - -AllowExpressions permits dangerous characters in -Command's scriptblock",
     )
 }
 
@@ -171,6 +476,75 @@ fn validate_script_path(script_path: &str) -> std::result::Result<(), String> {
     }
 }
 
+/// Validate an inline `-Command` scriptblock
+///
+/// # Arguments
+///
+/// * `command` - The inline scriptblock text passed to `-Command`
+/// * `allow_expressions` - Whether `-AllowExpressions` was given on the CLI
+///
+/// # Returns
+///
+/// `Ok(())` if the command block is safe to run, `Err` with description otherwise
+///
+/// # Security
+///
+/// `-Command` reintroduces shell-interpretation risk that `-Script` avoids,
+/// so this is stricter than `validate_parameters`: `DANGEROUS_CHARS` are
+/// rejected unless the caller opts in with `-AllowExpressions`, and the
+/// same `MAX_COMMAND_LENGTH` ceiling applies.
+fn validate_command_block(
+    command: &str,
+    allow_expressions: bool,
+) -> std::result::Result<(), String> {
+    if command.is_empty() {
+        return Err("Command block cannot be empty".to_string());
+    }
+
+    if command.len() > MAX_COMMAND_LENGTH {
+        return Err(format!(
+            "Command block exceeds maximum length ({} chars)",
+            MAX_COMMAND_LENGTH
+        ));
+    }
+
+    if !allow_expressions {
+        for dangerous_char in DANGEROUS_CHARS {
+            if command.contains(*dangerous_char) {
+                return Err(format!(
+                    "Command block contains forbidden character '{}' (pass -AllowExpressions to permit PowerShell expressions)",
+                    dangerous_char
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Extract the `-AllowExpressions` opt-in flag from the command line
+///
+/// # Returns
+///
+/// Whether the flag was present, together with the argument list stripped of it
+fn extract_allow_expressions(args: &[String]) -> (bool, Vec<String>) {
+    let mut allow_expressions = false;
+    let remaining: Vec<String> = args
+        .iter()
+        .filter(|arg| {
+            if arg.to_lowercase() == "-allowexpressions" {
+                allow_expressions = true;
+                false
+            } else {
+                true
+            }
+        })
+        .cloned()
+        .collect();
+
+    (allow_expressions, remaining)
+}
+
 /// Validate and sanitize parameters
 ///
 /// # Arguments
@@ -219,7 +593,11 @@ fn validate_parameters(params: &[String]) -> std::result::Result<(), String> {
     Ok(())
 }
 
-/// Get the full path to PowerShell executable
+/// Get the full path to the PowerShell executable to launch
+///
+/// # Arguments
+///
+/// * `mode` - Whether to prefer PowerShell 7+, force it, or force Windows PowerShell 5.1
 ///
 /// # Returns
 ///
@@ -227,15 +605,62 @@ fn validate_parameters(params: &[String]) -> std::result::Result<(), String> {
 ///
 /// # Security
 ///
-/// - Uses system directory to prevent PATH hijacking
-/// - Validates PowerShell executable exists
-/// - Uses Windows API to get system directory
+/// - Only ever returns fully-qualified paths that were verified to exist
+/// - Uses the Windows system directory for the 5.1 fallback to prevent PATH hijacking
+/// - PowerShell 7+ install roots are enumerated explicitly, never resolved via PATH
 #[cfg(windows)]
-fn get_powershell_path() -> std::result::Result<PathBuf, String> {
-    // Use hardcoded path - most reliable on Windows
+fn get_powershell_path(mode: PowerShellMode) -> std::result::Result<PathBuf, String> {
+    match mode {
+        PowerShellMode::WindowsPowerShell => get_windows_powershell_path(),
+        PowerShellMode::Core => find_pwsh_core().ok_or_else(|| {
+            "PowerShell 7+ (pwsh.exe) was not found in any known install location".to_string()
+        }),
+        PowerShellMode::Auto => match find_pwsh_core() {
+            Some(path) => Ok(path),
+            None => get_windows_powershell_path(),
+        },
+    }
+}
+
+/// Validate a `-Shell`/`windows-shell` interpreter override
+///
+/// # Security
+///
+/// Holds the override to the same bar `get_powershell_path` holds a
+/// discovered interpreter to: only a fully-qualified, existence-checked
+/// path is accepted. A bare or relative value (e.g. `-Shell pwsh`) would
+/// otherwise fall through to `CreateProcess`'s own PATH/CWD search,
+/// reintroducing the PATH-hijacking vulnerability this crate exists to
+/// prevent.
+fn validate_shell_override(path: &std::path::Path) -> std::result::Result<PathBuf, String> {
+    if !path.is_absolute() {
+        return Err(format!(
+            "-Shell/windows-shell must be an absolute path, got: {}",
+            path.display()
+        ));
+    }
+
+    if !path.is_file() {
+        return Err(format!(
+            "-Shell/windows-shell path not found: {}",
+            path.display()
+        ));
+    }
+
+    path.canonicalize()
+        .map_err(|e| format!("Failed to resolve shell path: {}", e))
+}
+
+/// Get the full path to the built-in Windows PowerShell 5.1 executable
+///
+/// # Security
+///
+/// - Uses the hardcoded system path to prevent PATH hijacking
+/// - Validates the executable exists before returning it
+#[cfg(windows)]
+fn get_windows_powershell_path() -> std::result::Result<PathBuf, String> {
     let ps_path = PathBuf::from(r"C:\Windows\System32\WindowsPowerShell\v1.0\powershell.exe");
 
-    // Validate PowerShell exists
     if !ps_path.exists() {
         return Err(format!(
             "PowerShell executable not found at: {}",
@@ -246,12 +671,113 @@ fn get_powershell_path() -> std::result::Result<PathBuf, String> {
     Ok(ps_path)
 }
 
-/// Execute PowerShell with the given script and parameters
+/// Search the well-known PowerShell 7+ install roots for the highest
+/// versioned `pwsh.exe`
+///
+/// Looks under `%ProgramFiles%\PowerShell\<version>`,
+/// `%ProgramFiles(x86)%\PowerShell\<version>` and the per-user
+/// `%LOCALAPPDATA%\Microsoft\WindowsApps\pwsh.exe` app-execution alias.
+///
+/// # Returns
+///
+/// `Some(PathBuf)` for the highest numeric version found, or `None` if no
+/// `pwsh.exe` exists in any known location
+#[cfg(windows)]
+fn find_pwsh_core() -> Option<PathBuf> {
+    let mut candidates: Vec<(PwshVersion, PathBuf)> = Vec::new();
+
+    for env_var in ["ProgramFiles", "ProgramFiles(x86)"] {
+        if let Ok(base) = env::var(env_var) {
+            collect_pwsh_candidates(&PathBuf::from(base).join("PowerShell"), &mut candidates);
+        }
+    }
+
+    // The per-user app-execution alias has no version directory; treat it as
+    // the lowest-priority candidate so any versioned install wins instead.
+    if let Ok(local_app_data) = env::var("LOCALAPPDATA") {
+        let alias = PathBuf::from(local_app_data).join(r"Microsoft\WindowsApps\pwsh.exe");
+        if alias.is_file() {
+            candidates.push((PwshVersion::new(vec![0]), alias));
+        }
+    }
+
+    candidates
+        .into_iter()
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, path)| path)
+}
+
+/// Scan one `...\PowerShell` root for versioned subdirectories containing `pwsh.exe`
+#[cfg(windows)]
+fn collect_pwsh_candidates(
+    powershell_root: &std::path::Path,
+    out: &mut Vec<(PwshVersion, PathBuf)>,
+) {
+    let Ok(entries) = std::fs::read_dir(powershell_root) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let dir_path = entry.path();
+        if !dir_path.is_dir() {
+            continue;
+        }
+
+        let Some(dir_name) = dir_path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        let Some(version) = PwshVersion::parse(dir_name) else {
+            continue;
+        };
+
+        let exe_path = dir_path.join("pwsh.exe");
+        if exe_path.is_file() {
+            out.push((version, exe_path));
+        }
+    }
+}
+
+/// A dotted version number (e.g. `7`, `7.1.5`, `10`) parsed from a
+/// PowerShell install directory name, ordered numerically component by
+/// component rather than lexicographically.
+#[cfg(windows)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PwshVersion(Vec<u64>);
+
+#[cfg(windows)]
+impl PwshVersion {
+    fn new(components: Vec<u64>) -> Self {
+        Self(components)
+    }
+
+    /// Parse a directory name like `7`, `7.1.5` or `10` into a version
+    ///
+    /// Returns `None` if the name is not a dotted sequence of integers.
+    fn parse(dir_name: &str) -> Option<Self> {
+        let components: std::result::Result<Vec<u64>, _> = dir_name
+            .split('.')
+            .map(|part| part.parse::<u64>())
+            .collect();
+        components.ok().filter(|c| !c.is_empty()).map(Self)
+    }
+
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+/// Number of trailing stderr lines retained for the final error report,
+/// bounding memory use regardless of how much a script emits
+const STDERR_TAIL_LINES: usize = 50;
+
+/// Execute PowerShell with the given script and parameters, streaming output live
 ///
 /// # Arguments
 ///
-/// * `powershell_path` - Full path to PowerShell executable
-/// * `script_path` - Path to the script to execute
+/// * `powershell_path` - Full path to the interpreter to launch
+/// * `shell_args` - Resolved invocation flags (the built-in default, or a `-ShellArg`/config override)
+/// * `invocation_value` - The script path (`-File` mode) or inline scriptblock (`-Command` mode)
 /// * `params` - Additional parameters to pass to the script
 ///
 /// # Returns
@@ -262,46 +788,101 @@ fn get_powershell_path() -> std::result::Result<PathBuf, String> {
 ///
 /// - Uses direct process creation (no shell expansion)
 /// - All arguments are properly escaped
-/// - PowerShell is run with restricted execution parameters
+/// - `shell_args` have already been through `validate_parameters` before reaching here
+///
+/// # Implementation
+///
+/// stdout/stderr are piped and drained on dedicated threads as the child
+/// produces them, rather than buffered in memory via `Command::output()`,
+/// so long-running scripts show progress immediately. Only a bounded tail
+/// of stderr is retained, for the error message if the script fails.
 fn execute_powershell(
     powershell_path: &PathBuf,
-    script_path: &str,
+    shell_args: &[String],
+    invocation_value: &str,
     params: &[String],
 ) -> std::result::Result<i32, String> {
     let mut cmd = Command::new(powershell_path);
 
-    // PowerShell security flags
-    cmd.arg("-NonInteractive")
-        .arg("-NoProfile")
-        .arg("-ExecutionPolicy")
-        .arg("Bypass")
-        .arg("-File")
-        .arg(script_path);
+    for arg in shell_args {
+        cmd.arg(arg);
+    }
+    cmd.arg(invocation_value);
 
     // Add script parameters
     for param in params {
         cmd.arg(param);
     }
 
-    // Execute and capture output for debugging
-    match cmd.output() {
-        Ok(output) => {
-            let exit_code = output.status.code().unwrap_or(1);
+    cmd.stdout(std::process::Stdio::piped());
+    cmd.stderr(std::process::Stdio::piped());
 
-            // If there was an error, show stderr
-            if exit_code != 0 && !output.stderr.is_empty() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(format!("PowerShell error (exit {}): {}", exit_code, stderr));
-            }
+    let mut child = cmd.spawn().map_err(|e| {
+        format!(
+            "Failed to execute PowerShell: {} (error code: {:?})",
+            e,
+            e.raw_os_error()
+        )
+    })?;
+
+    let child_stdout = child.stdout.take().expect("stdout was piped");
+    let child_stderr = child.stderr.take().expect("stderr was piped");
 
-            Ok(exit_code)
+    let stdout_thread = std::thread::spawn(move || {
+        for line in std::io::BufReader::new(child_stdout)
+            .lines()
+            .map_while(Result::ok)
+        {
+            println!("{}", line);
         }
-        Err(e) => Err(format!(
-            "Failed to execute PowerShell: {} (error code: {:?})",
+    });
+
+    let stderr_tail = std::sync::Arc::new(std::sync::Mutex::new(
+        std::collections::VecDeque::with_capacity(STDERR_TAIL_LINES),
+    ));
+    let stderr_tail_writer = std::sync::Arc::clone(&stderr_tail);
+    let stderr_thread = std::thread::spawn(move || {
+        for line in std::io::BufReader::new(child_stderr)
+            .lines()
+            .map_while(Result::ok)
+        {
+            eprintln!("{}", line);
+
+            let mut tail = stderr_tail_writer
+                .lock()
+                .expect("stderr tail mutex poisoned");
+            if tail.len() == STDERR_TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(line);
+        }
+    });
+
+    let status = child.wait().map_err(|e| {
+        format!(
+            "Failed to wait for PowerShell: {} (error code: {:?})",
             e,
             e.raw_os_error()
-        )),
+        )
+    })?;
+
+    let _ = stdout_thread.join();
+    let _ = stderr_thread.join();
+
+    let exit_code = status.code().unwrap_or(1);
+
+    if exit_code != 0 {
+        let tail = stderr_tail.lock().expect("stderr tail mutex poisoned");
+        if !tail.is_empty() {
+            let captured = tail.iter().cloned().collect::<Vec<_>>().join("\n");
+            return Err(format!(
+                "PowerShell error (exit {}): {}",
+                exit_code, captured
+            ));
+        }
     }
+
+    Ok(exit_code)
 }
 
 /// Display error message to user using Windows MessageBox
@@ -340,7 +921,7 @@ fn show_error(title: &str, message: &str) {
 ///
 /// Vector of u16 representing null-terminated UTF-16 string
 #[cfg(windows)]
-fn to_wide_string(s: &str) -> Vec<u16> {
+pub(crate) fn to_wide_string(s: &str) -> Vec<u16> {
     use std::ffi::OsStr;
     use std::os::windows::ffi::OsStrExt;
 
@@ -388,6 +969,16 @@ mod tests {
         assert!(validate_arguments(&args).is_ok());
     }
 
+    #[test]
+    fn test_validate_arguments_command_valid() {
+        let args = vec![
+            "program".to_string(),
+            "-Command".to_string(),
+            "Get-Process".to_string(),
+        ];
+        assert!(validate_arguments(&args).is_ok());
+    }
+
     #[test]
     fn test_validate_parameters_dangerous_semicolon() {
         let params = vec!["test;whoami".to_string()];
@@ -434,4 +1025,235 @@ mod tests {
         assert!(usage.contains("Examples"));
         assert!(usage.contains("-Script"));
     }
+
+    #[test]
+    fn test_split_launcher_args_no_trailing_params() {
+        let args = vec![
+            "program".to_string(),
+            "-Script".to_string(),
+            "script.ps1".to_string(),
+        ];
+        let (head, tail) = split_launcher_args(&args);
+        assert_eq!(head, args);
+        assert!(tail.is_empty());
+    }
+
+    #[test]
+    fn test_split_launcher_args_keeps_flag_lookalikes_in_tail() {
+        let args = vec![
+            "program".to_string(),
+            "-Script".to_string(),
+            r"C:\Approved\deploy.ps1".to_string(),
+            "-Shell".to_string(),
+            r"C:\Windows\Temp\evil.exe".to_string(),
+        ];
+        let (head, tail) = split_launcher_args(&args);
+        assert_eq!(
+            head,
+            vec![
+                "program".to_string(),
+                "-Script".to_string(),
+                r"C:\Approved\deploy.ps1".to_string(),
+            ]
+        );
+        assert_eq!(
+            tail,
+            vec![
+                "-Shell".to_string(),
+                r"C:\Windows\Temp\evil.exe".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_launcher_args_recognizes_flags_ahead_of_marker() {
+        let args = vec![
+            "program".to_string(),
+            "-Core".to_string(),
+            "-Script".to_string(),
+            "script.ps1".to_string(),
+            "-Core".to_string(),
+        ];
+        let (head, tail) = split_launcher_args(&args);
+        assert_eq!(
+            head,
+            vec![
+                "program".to_string(),
+                "-Core".to_string(),
+                "-Script".to_string(),
+                "script.ps1".to_string(),
+            ]
+        );
+        assert_eq!(tail, vec!["-Core".to_string()]);
+    }
+
+    #[test]
+    fn test_split_launcher_args_missing_marker() {
+        let args = vec!["program".to_string(), "-Core".to_string()];
+        let (head, tail) = split_launcher_args(&args);
+        assert_eq!(head, args);
+        assert!(tail.is_empty());
+    }
+
+    #[test]
+    fn test_split_launcher_args_marker_without_value() {
+        let args = vec!["program".to_string(), "-Script".to_string()];
+        let (head, tail) = split_launcher_args(&args);
+        assert_eq!(head, args);
+        assert!(tail.is_empty());
+    }
+
+    #[test]
+    fn test_extract_powershell_mode_default_auto() {
+        let args = vec![
+            "program".to_string(),
+            "-Script".to_string(),
+            "script.ps1".to_string(),
+        ];
+        let (mode, remaining) = extract_powershell_mode(&args);
+        assert_eq!(mode, PowerShellMode::Auto);
+        assert_eq!(remaining, args);
+    }
+
+    #[test]
+    fn test_extract_powershell_mode_core() {
+        let args = vec![
+            "program".to_string(),
+            "-Core".to_string(),
+            "-Script".to_string(),
+            "script.ps1".to_string(),
+        ];
+        let (mode, remaining) = extract_powershell_mode(&args);
+        assert_eq!(mode, PowerShellMode::Core);
+        assert_eq!(
+            remaining,
+            vec![
+                "program".to_string(),
+                "-Script".to_string(),
+                "script.ps1".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_powershell_mode_windows_powershell() {
+        let args = vec![
+            "program".to_string(),
+            "-WindowsPowerShell".to_string(),
+            "-Script".to_string(),
+            "script.ps1".to_string(),
+        ];
+        let (mode, _) = extract_powershell_mode(&args);
+        assert_eq!(mode, PowerShellMode::WindowsPowerShell);
+    }
+
+    #[test]
+    fn test_invocation_from_args_script() {
+        let args = vec![
+            "program".to_string(),
+            "-Script".to_string(),
+            "script.ps1".to_string(),
+        ];
+        match Invocation::from_args(&args) {
+            Invocation::Script(path) => assert_eq!(path, "script.ps1"),
+            Invocation::Command(_) => panic!("expected Script"),
+        }
+    }
+
+    #[test]
+    fn test_invocation_from_args_command() {
+        let args = vec![
+            "program".to_string(),
+            "-Command".to_string(),
+            "Get-Process".to_string(),
+        ];
+        match Invocation::from_args(&args) {
+            Invocation::Command(command) => assert_eq!(command, "Get-Process"),
+            Invocation::Script(_) => panic!("expected Command"),
+        }
+    }
+
+    #[test]
+    fn test_validate_command_block_empty() {
+        assert!(validate_command_block("", false).is_err());
+    }
+
+    #[test]
+    fn test_validate_command_block_too_long() {
+        let command = "a".repeat(MAX_COMMAND_LENGTH + 1);
+        assert!(validate_command_block(&command, false).is_err());
+    }
+
+    #[test]
+    fn test_validate_command_block_rejects_dangerous_chars_by_default() {
+        assert!(validate_command_block("Get-Process; whoami", false).is_err());
+    }
+
+    #[test]
+    fn test_validate_command_block_allows_dangerous_chars_with_opt_in() {
+        assert!(validate_command_block("Get-Process | Select-Object Name", true).is_ok());
+    }
+
+    #[test]
+    fn test_validate_command_block_accepts_plain_command() {
+        assert!(validate_command_block("Get-Process", false).is_ok());
+    }
+
+    #[test]
+    fn test_extract_allow_expressions_absent() {
+        let args = vec![
+            "program".to_string(),
+            "-Command".to_string(),
+            "Get-Process".to_string(),
+        ];
+        let (allow, remaining) = extract_allow_expressions(&args);
+        assert!(!allow);
+        assert_eq!(remaining, args);
+    }
+
+    #[test]
+    fn test_extract_allow_expressions_present() {
+        let args = vec![
+            "program".to_string(),
+            "-AllowExpressions".to_string(),
+            "-Command".to_string(),
+            "Get-Process | Select-Object Name".to_string(),
+        ];
+        let (allow, remaining) = extract_allow_expressions(&args);
+        assert!(allow);
+        assert_eq!(
+            remaining,
+            vec![
+                "program".to_string(),
+                "-Command".to_string(),
+                "Get-Process | Select-Object Name".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_default_shell_args_picks_file_flag_for_script() {
+        let invocation = Invocation::Script("script.ps1".to_string());
+        assert_eq!(default_shell_args(&invocation).last().unwrap(), "-File");
+    }
+
+    #[test]
+    fn test_default_shell_args_picks_command_flag_for_command() {
+        let invocation = Invocation::Command("Get-Process".to_string());
+        assert_eq!(default_shell_args(&invocation).last().unwrap(), "-Command");
+    }
+
+    #[test]
+    fn test_validate_shell_override_rejects_relative_path() {
+        let result = validate_shell_override(std::path::Path::new("pwsh.exe"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_shell_override_rejects_nonexistent_absolute_path() {
+        let result = validate_shell_override(std::path::Path::new(
+            r"C:\definitely\does\not\exist\pwsh.exe",
+        ));
+        assert!(result.is_err());
+    }
 }
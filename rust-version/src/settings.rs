@@ -0,0 +1,195 @@
+//! Shell interpreter and invocation-flag overrides
+//!
+//! By default `execute_powershell` launches the interpreter resolved by
+//! [`get_powershell_path`](crate::get_powershell_path) with a fixed set of
+//! security flags. This module lets a user override either the interpreter
+//! binary or its flags (or both) from the command line or a
+//! `ps-launcher.toml` placed next to the executable, following the same
+//! per-field precedence the `just` command runner uses for its
+//! `--shell`/`--shell-arg` overrides: explicit CLI values beat the config
+//! file, which beats the built-in default, and this is decided independently
+//! for the shell binary and for the argument vector rather than all-or-nothing.
+
+use std::path::PathBuf;
+
+use crate::config;
+
+/// User-resolvable overrides for the interpreter and its invocation flags
+///
+/// Both fields are independently optional so a config file can set
+/// `windows-shell` while the CLI overrides only `-ShellArg`, or vice versa.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Settings {
+    /// Overrides the interpreter binary normally resolved by `get_powershell_path`
+    pub shell: Option<PathBuf>,
+    /// Overrides the fixed `-NonInteractive -NoProfile ... -File`/`-Command` flag set
+    pub shell_args: Option<Vec<String>>,
+}
+
+impl Settings {
+    /// Merge this (higher-precedence) layer over a lower-precedence fallback
+    ///
+    /// Each field is resolved independently: a value present here wins,
+    /// otherwise the fallback's value is used.
+    pub fn merge(self, fallback: Settings) -> Settings {
+        Settings {
+            shell: self.shell.or(fallback.shell),
+            shell_args: self.shell_args.or(fallback.shell_args),
+        }
+    }
+}
+
+/// Extract `-Shell <path>` and repeatable `-ShellArg <arg>` from the command line
+///
+/// # Arguments
+///
+/// * `args` - Raw command line arguments including program name
+///
+/// # Returns
+///
+/// The `Settings` requested on the CLI together with the argument list
+/// stripped of the recognized flags and their values, so the rest of the
+/// pipeline can keep validating positionally as if they were never there.
+pub fn extract_cli_settings(args: &[String]) -> (Settings, Vec<String>) {
+    let mut shell: Option<PathBuf> = None;
+    let mut shell_args: Vec<String> = Vec::new();
+    let mut shell_args_given = false;
+    let mut remaining: Vec<String> = Vec::with_capacity(args.len());
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].to_lowercase().as_str() {
+            "-shell" if i + 1 < args.len() => {
+                shell = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            }
+            "-shellarg" if i + 1 < args.len() => {
+                shell_args_given = true;
+                shell_args.push(args[i + 1].clone());
+                i += 2;
+            }
+            _ => {
+                remaining.push(args[i].clone());
+                i += 1;
+            }
+        }
+    }
+
+    let settings = Settings {
+        shell,
+        shell_args: shell_args_given.then_some(shell_args),
+    };
+
+    (settings, remaining)
+}
+
+/// Load overrides from `ps-launcher.toml` next to the running executable
+///
+/// Missing file, unreadable file, or a file with neither recognized key all
+/// resolve to `Settings::default()` - a config file is entirely optional.
+pub fn load_config_settings() -> Settings {
+    match config::read_config_file() {
+        Some(contents) => parse_config(&contents),
+        None => Settings::default(),
+    }
+}
+
+/// Parse the `windows-shell` and `shell-args` keys from a config file's contents
+fn parse_config(contents: &str) -> Settings {
+    let shell = config::find_key(contents, "windows-shell")
+        .and_then(config::parse_toml_string)
+        .map(PathBuf::from);
+    let shell_args =
+        config::find_key(contents, "shell-args").and_then(config::parse_toml_string_array);
+
+    Settings { shell, shell_args }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_cli_settings_none() {
+        let args = vec!["program".to_string(), "-Script".to_string()];
+        let (settings, remaining) = extract_cli_settings(&args);
+        assert_eq!(settings, Settings::default());
+        assert_eq!(remaining, args);
+    }
+
+    #[test]
+    fn test_extract_cli_settings_shell() {
+        let args = vec![
+            "program".to_string(),
+            "-Shell".to_string(),
+            "C:\\pwsh.exe".to_string(),
+            "-Script".to_string(),
+        ];
+        let (settings, remaining) = extract_cli_settings(&args);
+        assert_eq!(settings.shell, Some(PathBuf::from("C:\\pwsh.exe")));
+        assert_eq!(
+            remaining,
+            vec!["program".to_string(), "-Script".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_extract_cli_settings_shell_args_repeatable() {
+        let args = vec![
+            "program".to_string(),
+            "-ShellArg".to_string(),
+            "-NoProfile".to_string(),
+            "-ShellArg".to_string(),
+            "-NonInteractive".to_string(),
+            "-Script".to_string(),
+        ];
+        let (settings, _) = extract_cli_settings(&args);
+        assert_eq!(
+            settings.shell_args,
+            Some(vec![
+                "-NoProfile".to_string(),
+                "-NonInteractive".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_settings_merge_precedence_is_per_field() {
+        let cli = Settings {
+            shell: Some(PathBuf::from("C:\\cli-pwsh.exe")),
+            shell_args: None,
+        };
+        let config = Settings {
+            shell: Some(PathBuf::from("C:\\config-pwsh.exe")),
+            shell_args: Some(vec!["-Verbose".to_string()]),
+        };
+
+        let merged = cli.merge(config);
+        assert_eq!(merged.shell, Some(PathBuf::from("C:\\cli-pwsh.exe")));
+        assert_eq!(merged.shell_args, Some(vec!["-Verbose".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_config_both_keys() {
+        let toml = "windows-shell = \"C:\\\\PowerShell\\\\7\\\\pwsh.exe\"\nshell-args = [\"-NoProfile\", \"-NonInteractive\"]\n";
+        let settings = parse_config(toml);
+        assert_eq!(
+            settings.shell,
+            Some(PathBuf::from("C:\\PowerShell\\7\\pwsh.exe"))
+        );
+        assert_eq!(
+            settings.shell_args,
+            Some(vec![
+                "-NoProfile".to_string(),
+                "-NonInteractive".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_config_ignores_unknown_keys_and_comments() {
+        let toml = "# a comment\nunknown-key = \"value\"\n";
+        let settings = parse_config(toml);
+        assert_eq!(settings, Settings::default());
+    }
+}
@@ -0,0 +1,196 @@
+//! Authenticode signature verification gate
+//!
+//! When `-RequireSignature` is passed, a script must carry a valid
+//! Authenticode signature before `execute_powershell` is allowed to run it.
+//! Verification shells out to `Get-AuthenticodeSignature` on the already
+//! resolved PowerShell interpreter rather than hand-rolling a WinTrust/
+//! WinVerifyTrust FFI binding, in keeping with the rest of the launcher's
+//! approach of driving PowerShell itself rather than reimplementing its
+//! functionality.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::config;
+
+/// Verify a script carries a valid Authenticode signature
+///
+/// # Arguments
+///
+/// * `powershell_path` - Interpreter used to run the verification query
+/// * `script_path` - Canonicalized path to the script to verify
+/// * `pinned_thumbprints` - If non-empty, the signer's thumbprint must be one of these
+///
+/// # Returns
+///
+/// `Ok(())` if the signature status is `Valid` (and, when pinning is
+/// active, the signer thumbprint matches); `Err` with a description otherwise
+pub fn verify_signature(
+    powershell_path: &Path,
+    script_path: &Path,
+    pinned_thumbprints: &[String],
+) -> Result<(), String> {
+    let escaped_path = script_path.display().to_string().replace('\'', "''");
+    let query = format!(
+        "$sig = Get-AuthenticodeSignature -LiteralPath '{}'; \"$($sig.Status)|$($sig.SignerCertificate.Thumbprint)\"",
+        escaped_path
+    );
+
+    let output = Command::new(powershell_path)
+        .arg("-NonInteractive")
+        .arg("-NoProfile")
+        .arg("-ExecutionPolicy")
+        .arg("Bypass")
+        .arg("-Command")
+        .arg(&query)
+        .output()
+        .map_err(|e| format!("Failed to invoke signature check: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Signature check failed to run (exit {}): {}",
+            output.status.code().unwrap_or(-1),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_signature_result(stdout.trim(), pinned_thumbprints)
+}
+
+/// Interpret the `Status|Thumbprint` line produced by the verification query
+fn parse_signature_result(result_line: &str, pinned_thumbprints: &[String]) -> Result<(), String> {
+    let mut parts = result_line.splitn(2, '|');
+    let status = parts.next().unwrap_or("").trim();
+    let thumbprint = parts.next().unwrap_or("").trim();
+
+    if status != "Valid" {
+        return Err(format!(
+            "Script does not carry a valid Authenticode signature (status: {})",
+            if status.is_empty() { "Unknown" } else { status }
+        ));
+    }
+
+    if !pinned_thumbprints.is_empty()
+        && !pinned_thumbprints
+            .iter()
+            .any(|pinned| pinned.eq_ignore_ascii_case(thumbprint))
+    {
+        return Err(format!(
+            "Script is signed, but its certificate thumbprint ({}) is not in the pinned allowlist",
+            thumbprint
+        ));
+    }
+
+    Ok(())
+}
+
+/// Extract `-RequireSignature` and repeatable `-PinThumbprint <thumbprint>` from the command line
+///
+/// # Returns
+///
+/// Whether `-RequireSignature` was present, the CLI-pinned thumbprints, and
+/// the argument list with both flags (and their values) stripped out.
+pub fn extract_cli_options(args: &[String]) -> (bool, Vec<String>, Vec<String>) {
+    let mut require_signature = false;
+    let mut pinned_thumbprints = Vec::new();
+    let mut remaining = Vec::with_capacity(args.len());
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].to_lowercase().as_str() {
+            "-requiresignature" => {
+                require_signature = true;
+                i += 1;
+            }
+            "-pinthumbprint" if i + 1 < args.len() => {
+                pinned_thumbprints.push(args[i + 1].to_uppercase());
+                i += 2;
+            }
+            _ => {
+                remaining.push(args[i].clone());
+                i += 1;
+            }
+        }
+    }
+
+    (require_signature, pinned_thumbprints, remaining)
+}
+
+/// Read the `pinned-thumbprints` array from `ps-launcher.toml`, if present
+pub fn configured_pinned_thumbprints() -> Vec<String> {
+    let Some(contents) = config::read_config_file() else {
+        return Vec::new();
+    };
+
+    config::find_key(&contents, "pinned-thumbprints")
+        .and_then(config::parse_toml_string_array)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|thumbprint| thumbprint.to_uppercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_cli_options_none() {
+        let args = vec!["program".to_string(), "-Script".to_string()];
+        let (require_signature, pinned, remaining) = extract_cli_options(&args);
+        assert!(!require_signature);
+        assert!(pinned.is_empty());
+        assert_eq!(remaining, args);
+    }
+
+    #[test]
+    fn test_extract_cli_options_require_signature() {
+        let args = vec![
+            "program".to_string(),
+            "-RequireSignature".to_string(),
+            "-Script".to_string(),
+        ];
+        let (require_signature, _, remaining) = extract_cli_options(&args);
+        assert!(require_signature);
+        assert_eq!(
+            remaining,
+            vec!["program".to_string(), "-Script".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_extract_cli_options_pin_thumbprint_repeatable() {
+        let args = vec![
+            "program".to_string(),
+            "-PinThumbprint".to_string(),
+            "abc123".to_string(),
+            "-PinThumbprint".to_string(),
+            "def456".to_string(),
+        ];
+        let (_, pinned, _) = extract_cli_options(&args);
+        assert_eq!(pinned, vec!["ABC123".to_string(), "DEF456".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_signature_result_valid_no_pinning() {
+        assert!(parse_signature_result("Valid|ABCDEF", &[]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_signature_result_rejects_not_signed() {
+        assert!(parse_signature_result("NotSigned|", &[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_signature_result_rejects_unpinned_thumbprint() {
+        let pinned = vec!["AAA111".to_string()];
+        assert!(parse_signature_result("Valid|BBB222", &pinned).is_err());
+    }
+
+    #[test]
+    fn test_parse_signature_result_accepts_pinned_thumbprint_case_insensitive() {
+        let pinned = vec!["aaa111".to_string()];
+        assert!(parse_signature_result("Valid|AAA111", &pinned).is_ok());
+    }
+}
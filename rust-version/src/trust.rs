@@ -0,0 +1,199 @@
+//! Script trust/allowlist gate
+//!
+//! Decides whether a requested script may run without an interactive
+//! confirmation, modeled on how terminals auto-approve only known-safe
+//! commandlines: the script path is environment-expanded and canonicalized,
+//! then it is trusted only if that canonical path sits under a configured
+//! trusted root *and* the raw invocation carries no extra embedded command
+//! tokens (e.g. `wsl.exe -d Ubuntu` is refused even though `wsl.exe` alone
+//! is allowed). Anything else falls back to a `MessageBoxW` confirmation
+//! rather than silently executing.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::config;
+
+#[cfg(windows)]
+use windows::{
+    core::PCWSTR,
+    Win32::System::Environment::ExpandEnvironmentStringsW,
+    Win32::UI::WindowsAndMessaging::{MessageBoxW, IDYES, MB_ICONWARNING, MB_YESNO},
+};
+
+/// Known executable/script extensions that mark a later whitespace-separated
+/// token as a plausible second command rather than part of a path
+const EMBEDDED_COMMAND_EXTENSIONS: &[&str] = &[".exe", ".ps1", ".bat", ".cmd"];
+
+/// Decide whether a script path is trusted to run without confirmation
+///
+/// # Arguments
+///
+/// * `raw_script_path` - The `-Script` value exactly as passed on the command line
+/// * `trusted_roots` - Directories a trusted script must canonicalize to a descendant of
+#[cfg(windows)]
+pub fn is_trusted(raw_script_path: &str, trusted_roots: &[PathBuf]) -> bool {
+    if has_embedded_command_tokens(raw_script_path) {
+        return false;
+    }
+
+    let expanded = expand_env_vars(raw_script_path);
+    let Ok(canonical) = Path::new(&expanded).canonicalize() else {
+        return false;
+    };
+
+    trusted_roots.iter().any(|root| {
+        root.canonicalize()
+            .map(|canonical_root| canonical.starts_with(canonical_root))
+            .unwrap_or(false)
+    })
+}
+
+/// Reject scripts whose raw string looks like it smuggles a second command
+///
+/// A legitimate path can contain spaces (e.g. `C:\Program Files\...`), so a
+/// trailing token ending in an executable extension is fine - that's just
+/// the end of the single path. What's suspicious is a *non-trailing* token
+/// that already looks like a complete executable/script path (e.g.
+/// `test.ps1 C:\evil.exe`), or any token that looks like a CLI switch (e.g.
+/// `wsl.exe -d Ubuntu`).
+fn has_embedded_command_tokens(raw_script_path: &str) -> bool {
+    let tokens: Vec<&str> = raw_script_path.split_whitespace().collect();
+    let Some((last, rest)) = tokens.split_last() else {
+        return false;
+    };
+
+    if !rest.is_empty() && (last.starts_with('-') || last.starts_with('/')) {
+        return true;
+    }
+
+    rest.iter().any(|token| {
+        token.starts_with('-')
+            || token.starts_with('/')
+            || EMBEDDED_COMMAND_EXTENSIONS
+                .iter()
+                .any(|ext| token.to_lowercase().ends_with(ext))
+    })
+}
+
+/// The default trusted roots: the directory containing ps-launcher.exe, plus
+/// any `trusted-roots` listed in `ps-launcher.toml`
+pub fn default_trusted_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+
+    if let Ok(exe_path) = env::current_exe() {
+        if let Some(exe_dir) = exe_path.parent() {
+            roots.push(exe_dir.to_path_buf());
+        }
+    }
+
+    roots.extend(configured_trusted_roots());
+    roots
+}
+
+/// Read the `trusted-roots` array from `ps-launcher.toml`, if present
+fn configured_trusted_roots() -> Vec<PathBuf> {
+    let Some(contents) = config::read_config_file() else {
+        return Vec::new();
+    };
+
+    config::find_key(&contents, "trusted-roots")
+        .and_then(config::parse_toml_string_array)
+        .unwrap_or_default()
+        .into_iter()
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Expand `%VAR%`-style environment references in a path using the Windows API
+///
+/// Exposed beyond this module so callers that need to act on the script
+/// path before `is_trusted` runs (e.g. existence checks) see the same
+/// expansion `is_trusted` itself applies internally.
+#[cfg(windows)]
+pub(crate) fn expand_env_vars(path: &str) -> String {
+    let wide_path = crate::to_wide_string(path);
+
+    unsafe {
+        let needed = ExpandEnvironmentStringsW(PCWSTR(wide_path.as_ptr()), None);
+        if needed == 0 {
+            return path.to_string();
+        }
+
+        let mut buffer = vec![0u16; needed as usize];
+        let written = ExpandEnvironmentStringsW(PCWSTR(wide_path.as_ptr()), Some(&mut buffer));
+        if written == 0 {
+            return path.to_string();
+        }
+
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        String::from_utf16_lossy(&buffer[..len])
+    }
+}
+
+/// Ask the user to confirm running a script that is not trusted
+///
+/// # Returns
+///
+/// `true` if the user chose "Yes", `false` otherwise
+#[cfg(windows)]
+pub fn confirm_untrusted_script(script_path: &str) -> bool {
+    let message = format!(
+        "This script is not in a trusted location:\n\n{}\n\nRun it anyway?",
+        script_path
+    );
+
+    unsafe {
+        let result = MessageBoxW(
+            None,
+            PCWSTR(crate::to_wide_string(&message).as_ptr()),
+            PCWSTR(crate::to_wide_string("Untrusted Script").as_ptr()),
+            MB_YESNO | MB_ICONWARNING,
+        );
+        result == IDYES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_embedded_command_tokens_plain_path() {
+        assert!(!has_embedded_command_tokens(r"C:\Scripts\test.ps1"));
+    }
+
+    #[test]
+    fn test_has_embedded_command_tokens_path_with_spaces() {
+        assert!(!has_embedded_command_tokens(
+            r"C:\Program Files\Scripts\test.ps1"
+        ));
+    }
+
+    #[test]
+    fn test_has_embedded_command_tokens_rejects_trailing_switch() {
+        assert!(has_embedded_command_tokens(r"wsl.exe -d Ubuntu"));
+    }
+
+    #[test]
+    fn test_has_embedded_command_tokens_rejects_second_executable() {
+        assert!(has_embedded_command_tokens(
+            r"C:\Scripts\test.ps1 C:\evil.exe"
+        ));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_is_trusted_rejects_embedded_command_before_path_checks() {
+        assert!(!is_trusted("wsl.exe -d Ubuntu", &[PathBuf::from(".")]));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_is_trusted_rejects_nonexistent_path() {
+        assert!(!is_trusted(
+            r"C:\definitely\does\not\exist.ps1",
+            &[PathBuf::from(r"C:\definitely")]
+        ));
+    }
+}